@@ -5,6 +5,175 @@ use rafx::api::ash;
 use ash::vk;
 use ash::version::InstanceV1_0;
 use rafx::api::vulkan::RafxRawImageVulkan;
+#[cfg(target_os = "macos")]
+use rafx::api::metal::RafxRawImageMetal;
+
+/// A single denylisted (vendor, driver-version-range) rule. `below`/`above` are compared
+/// against the raw `driverVersion` reported by `vkGetPhysicalDeviceProperties`, which is not
+/// comparable across vendors, so a rule only ever matches within its own `vendor_id`.
+#[derive(Debug, Clone, Copy)]
+pub struct VkDenylistEntry {
+    pub vendor_id: u32,
+    pub driver_version_below: Option<u32>,
+    pub driver_version_above: Option<u32>,
+}
+
+impl VkDenylistEntry {
+    fn matches(
+        &self,
+        vendor_id: u32,
+        driver_version: u32,
+    ) -> bool {
+        if self.vendor_id != vendor_id {
+            return false;
+        }
+
+        if let Some(below) = self.driver_version_below {
+            if driver_version >= below {
+                return false;
+            }
+        }
+
+        if let Some(above) = self.driver_version_above {
+            if driver_version <= above {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+// Known-bad Vulkan drivers that render Skia incorrectly or crash outright. Entries are vendor
+// IDs as reported by vkGetPhysicalDeviceProperties; see https://pcisig.com/membership/member-companies
+// for the PCI vendor ID registry these come from (plus the small block of Khronos-reserved IDs
+// for non-hardware implementations, e.g. 0x10005 below).
+//
+// Deliberately just one entry, and it's vendor-only (no driverVersion range): per-vendor
+// driverVersion encodings vary (e.g. Intel packs its own major<<14|minor layout on Windows
+// rather than `vk::make_version`'s major.minor.patch), so a baked-in version threshold risks
+// silently matching - or missing - the wrong driver builds. A bare vendor ID has no such
+// ambiguity. Callers that know a verified version range for their own hardware should add it via
+// `VkSkiaContextConfig::with_denylist_entry`.
+fn default_denylist() -> Vec<VkDenylistEntry> {
+    vec![
+        VkDenylistEntry {
+            // VK_VENDOR_ID_MESA: Mesa's llvmpipe/lavapipe software Vulkan implementation. It
+            // reports a complete, valid physical device but there's no GPU behind it, so Skia's
+            // GPU-accelerated backend buys nothing here and the raster fallback is strictly
+            // better - this holds across every driver version, hence no version range.
+            vendor_id: 0x10005,
+            driver_version_below: None,
+            driver_version_above: None,
+        },
+    ]
+}
+
+#[cfg(test)]
+mod denylist_tests {
+    use super::VkDenylistEntry;
+
+    #[test]
+    fn below_only_matches_versions_strictly_below_threshold() {
+        let entry = VkDenylistEntry {
+            vendor_id: 0x8086,
+            driver_version_below: Some(100),
+            driver_version_above: None,
+        };
+
+        assert!(entry.matches(0x8086, 50));
+        assert!(!entry.matches(0x8086, 100));
+        assert!(!entry.matches(0x8086, 150));
+    }
+
+    #[test]
+    fn above_only_matches_versions_strictly_above_threshold() {
+        let entry = VkDenylistEntry {
+            vendor_id: 0x8086,
+            driver_version_below: None,
+            driver_version_above: Some(100),
+        };
+
+        assert!(!entry.matches(0x8086, 50));
+        assert!(!entry.matches(0x8086, 100));
+        assert!(entry.matches(0x8086, 150));
+    }
+
+    #[test]
+    fn both_bounds_match_only_the_open_interval_between_them() {
+        let entry = VkDenylistEntry {
+            vendor_id: 0x8086,
+            driver_version_below: Some(200),
+            driver_version_above: Some(100),
+        };
+
+        assert!(!entry.matches(0x8086, 100));
+        assert!(entry.matches(0x8086, 150));
+        assert!(!entry.matches(0x8086, 200));
+    }
+
+    #[test]
+    fn wrong_vendor_never_matches_regardless_of_version() {
+        let entry = VkDenylistEntry {
+            vendor_id: 0x8086,
+            driver_version_below: None,
+            driver_version_above: None,
+        };
+
+        assert!(!entry.matches(0x10de, 0));
+    }
+}
+
+/// Configuration for [`VkSkiaContext::new`], currently just the GPU/driver denylist.
+#[derive(Clone)]
+pub struct VkSkiaContextConfig {
+    denylist: Vec<VkDenylistEntry>,
+}
+
+impl VkSkiaContextConfig {
+    /// Starts from the built-in default denylist.
+    pub fn new() -> Self {
+        VkSkiaContextConfig {
+            denylist: default_denylist(),
+        }
+    }
+
+    /// An empty denylist; nothing is ever rejected.
+    pub fn empty() -> Self {
+        VkSkiaContextConfig { denylist: vec![] }
+    }
+
+    pub fn with_denylist_entry(
+        mut self,
+        entry: VkDenylistEntry,
+    ) -> Self {
+        self.denylist.push(entry);
+        self
+    }
+
+    fn is_denied(
+        &self,
+        vendor_id: u32,
+        driver_version: u32,
+    ) -> bool {
+        self.denylist
+            .iter()
+            .any(|entry| entry.matches(vendor_id, driver_version))
+    }
+}
+
+impl Default for VkSkiaContextConfig {
+    fn default() -> Self {
+        VkSkiaContextConfig::new()
+    }
+}
+
+/// Result of attempting to set up a vulkan-backed skia context. A denied/unsupported device
+/// does not panic; the caller is expected to fall back to a CPU raster backend instead.
+pub enum VkSkiaContextCreateResult {
+    Vulkan(VkSkiaContext),
+    Denied,
+}
 
 /// Handles setting up skia to use the same vulkan instance we initialize
 pub struct VkSkiaContext {
@@ -15,7 +184,8 @@ impl VkSkiaContext {
     pub fn new(
         device_context: &RafxDeviceContext,
         queue: &RafxQueue,
-    ) -> Self {
+        config: &VkSkiaContextConfig,
+    ) -> VkSkiaContextCreateResult {
         use vk::Handle;
 
         let vk_device_context = device_context.vk_device_context().unwrap();
@@ -24,6 +194,21 @@ impl VkSkiaContext {
         let physical_device = vk_device_context.physical_device();
         let device = vk_device_context.device();
 
+        let properties = unsafe { instance.get_physical_device_properties(physical_device) };
+        let device_name = unsafe {
+            std::ffi::CStr::from_ptr(properties.device_name.as_ptr())
+                .to_string_lossy()
+                .into_owned()
+        };
+
+        if config.is_denied(properties.vendor_id, properties.driver_version) {
+            warn!(
+                "Vulkan device '{}' (vendor {:#x}, device {:#x}, driver {:#x}) is denylisted, falling back to raster",
+                device_name, properties.vendor_id, properties.device_id, properties.driver_version
+            );
+            return VkSkiaContextCreateResult::Denied;
+        }
+
         let graphics_queue_family = vk_device_context.queue_family_indices().graphics_queue_family_index;
 
         let get_proc = |of| unsafe {
@@ -37,8 +222,8 @@ impl VkSkiaContext {
         };
 
         info!(
-            "Setting up skia backend context with queue family index {}",
-            graphics_queue_family
+            "Setting up skia backend context with queue family index {} on device '{}'",
+            graphics_queue_family, device_name
         );
 
         let backend_context = unsafe {
@@ -55,9 +240,13 @@ impl VkSkiaContext {
             )
         };
 
-        let context = skia_safe::gpu::Context::new_vulkan(&backend_context).unwrap();
-
-        VkSkiaContext { context }
+        match skia_safe::gpu::Context::new_vulkan(&backend_context) {
+            Some(context) => VkSkiaContextCreateResult::Vulkan(VkSkiaContext { context }),
+            None => {
+                warn!("skia_safe::gpu::Context::new_vulkan returned None, falling back to raster");
+                VkSkiaContextCreateResult::Denied
+            }
+        }
     }
 
     // We must not return vulkan 1.2 because skia compiles VMA with support only up to 1.1 and will
@@ -102,6 +291,55 @@ pub struct VkSkiaSurface {
     pub image_view: ResourceArc<ImageViewResource>,
     pub surface: skia_safe::Surface,
     pub texture: skia_safe::gpu::BackendTexture,
+    pub color_type: skia_safe::ColorType,
+    pub format: RafxFormat,
+    pub color_space: VkSkiaColorSpace,
+}
+
+/// A ready-made preference list to pass as `preferred_color_types` to [`VkSkiaSurface::new`],
+/// which probes it in order. Covers the common 8-bit case, 10-bit for wide gamut, and F16 for HDR.
+pub fn default_color_type_preferences() -> Vec<skia_safe::ColorType> {
+    vec![
+        skia_safe::ColorType::n32(),
+        skia_safe::ColorType::RGBA8888,
+        skia_safe::ColorType::BGRA8888,
+        skia_safe::ColorType::RGBA1010102,
+        skia_safe::ColorType::RGBAF16,
+    ]
+}
+
+/// Selectable skia color space for a [`VkSkiaSurface`]. `SrgbLinear` is the crate's historical
+/// default (linear transfer function, premultiplied alpha) and is what you get if you don't
+/// otherwise need perceptual sRGB or wide-gamut output.
+///
+/// Skia alone owns the sRGB encode here: the backing `RafxFormat` is never an `_SRGB` variant
+/// (it is `_UNORM`, or `_SFLOAT` for F16), regardless of which color space is selected, and it's skia's `ColorSpace` (set from this enum
+/// below) that decides whether and how pixel values get gamma-encoded as they're written into
+/// the surface. The hardware never also applies its own gamma (which an `_SRGB` format would
+/// trigger on top of skia's encode) - that would double the transfer function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VkSkiaColorSpace {
+    SrgbLinear,
+    Srgb,
+    DisplayP3,
+}
+
+impl Default for VkSkiaColorSpace {
+    fn default() -> Self {
+        VkSkiaColorSpace::SrgbLinear
+    }
+}
+
+impl VkSkiaColorSpace {
+    fn to_skia(self) -> skia_safe::ColorSpace {
+        match self {
+            VkSkiaColorSpace::SrgbLinear => skia_safe::ColorSpace::new_srgb_linear(),
+            VkSkiaColorSpace::Srgb => skia_safe::ColorSpace::new_srgb(),
+            VkSkiaColorSpace::DisplayP3 => {
+                skia_safe::ColorSpace::new_rgb(&skia_safe::NamedTransferFn::SRGB, &skia_safe::NamedGamut::DISPLAY_P3)
+            }
+        }
+    }
 }
 
 impl VkSkiaSurface {
@@ -109,14 +347,506 @@ impl VkSkiaSurface {
         unsafe { std::mem::transmute(texture.vulkan_image_info().unwrap().image) }
     }
 
+    // The Rafx format that backs each color type we know how to negotiate. `None` means skia
+    // supports the color type but we have no matching Rafx format to hand the image view.
+    fn color_type_to_format(color_type: skia_safe::ColorType) -> Option<RafxFormat> {
+        match color_type {
+            skia_safe::ColorType::RGBA8888 => Some(RafxFormat::R8G8B8A8_UNORM),
+            skia_safe::ColorType::BGRA8888 => Some(RafxFormat::B8G8R8A8_UNORM),
+            skia_safe::ColorType::RGBA1010102 => Some(RafxFormat::R10G10B10A2_UNORM),
+            skia_safe::ColorType::RGBAF16 => Some(RafxFormat::R16G16B16A16_SFLOAT),
+            _ => None,
+        }
+    }
+
+    // Probes `preferred` in order and returns the first one skia reports as renderable as a
+    // surface on this device, along with its matching Rafx format.
+    fn negotiate_color_type(
+        context: &mut VkSkiaContext,
+        preferred: &[skia_safe::ColorType],
+    ) -> RafxResult<(skia_safe::ColorType, RafxFormat)> {
+        for &color_type in preferred {
+            let format = match Self::color_type_to_format(color_type) {
+                Some(format) => format,
+                None => continue,
+            };
+
+            if context.context.color_type_supported_as_surface(color_type) {
+                return Ok((color_type, format));
+            }
+        }
+
+        Err(RafxError::StringError(
+            "None of the preferred color types are supported as a surface on this device"
+                .to_string(),
+        ))
+    }
+
+    fn sample_count_to_vk(sample_count: RafxSampleCount) -> vk::SampleCountFlags {
+        match sample_count {
+            RafxSampleCount::SampleCount1 => vk::SampleCountFlags::TYPE_1,
+            RafxSampleCount::SampleCount2 => vk::SampleCountFlags::TYPE_2,
+            RafxSampleCount::SampleCount4 => vk::SampleCountFlags::TYPE_4,
+            RafxSampleCount::SampleCount8 => vk::SampleCountFlags::TYPE_8,
+            RafxSampleCount::SampleCount16 => vk::SampleCountFlags::TYPE_16,
+            RafxSampleCount::SampleCount32 => vk::SampleCountFlags::TYPE_32,
+        }
+    }
+
+    fn sample_count_to_usize(sample_count: RafxSampleCount) -> usize {
+        match sample_count {
+            RafxSampleCount::SampleCount1 => 1,
+            RafxSampleCount::SampleCount2 => 2,
+            RafxSampleCount::SampleCount4 => 4,
+            RafxSampleCount::SampleCount8 => 8,
+            RafxSampleCount::SampleCount16 => 16,
+            RafxSampleCount::SampleCount32 => 32,
+        }
+    }
+
+    fn next_lower_sample_count(sample_count: RafxSampleCount) -> Option<RafxSampleCount> {
+        match sample_count {
+            RafxSampleCount::SampleCount32 => Some(RafxSampleCount::SampleCount16),
+            RafxSampleCount::SampleCount16 => Some(RafxSampleCount::SampleCount8),
+            RafxSampleCount::SampleCount8 => Some(RafxSampleCount::SampleCount4),
+            RafxSampleCount::SampleCount4 => Some(RafxSampleCount::SampleCount2),
+            RafxSampleCount::SampleCount2 => Some(RafxSampleCount::SampleCount1),
+            RafxSampleCount::SampleCount1 => None,
+        }
+    }
+
+    // Clamps `requested` down to the nearest sample count this physical device's color
+    // framebuffers actually support, falling back to no multisampling if even that's missing.
+    fn clamp_sample_count(
+        device_context: &RafxDeviceContext,
+        requested: RafxSampleCount,
+    ) -> RafxSampleCount {
+        let vk_device_context = device_context.vk_device_context().unwrap();
+        let properties = unsafe {
+            vk_device_context
+                .instance()
+                .get_physical_device_properties(vk_device_context.physical_device())
+        };
+        let supported = properties.limits.framebuffer_color_sample_counts;
+
+        let mut candidate = requested;
+        loop {
+            if supported.contains(Self::sample_count_to_vk(candidate)) {
+                return candidate;
+            }
+
+            match Self::next_lower_sample_count(candidate) {
+                Some(next) => candidate = next,
+                None => return RafxSampleCount::SampleCount1,
+            }
+        }
+    }
+
     pub fn new(
         resource_manager: &ResourceManager,
         context: &mut VkSkiaContext,
         extents: RafxExtents2D,
+        sample_count: RafxSampleCount,
+        preferred_color_types: &[skia_safe::ColorType],
+        color_space: VkSkiaColorSpace,
+    ) -> RafxResult<Self> {
+        let (color_type, format) = Self::negotiate_color_type(context, preferred_color_types)?;
+
+        let alpha_type = skia_safe::AlphaType::Premul;
+        let skia_color_space = Some(color_space.to_skia());
+
+        let image_info = skia_safe::ImageInfo::new(
+            (extents.width as i32, extents.height as i32),
+            color_type,
+            alpha_type,
+            skia_color_space,
+        );
+
+        let device_context = resource_manager.device_context();
+        let sample_count = Self::clamp_sample_count(device_context, sample_count);
+
+        let mut surface = skia_safe::Surface::new_render_target(
+            &mut context.context,
+            skia_safe::Budgeted::Yes,
+            &image_info,
+            Some(Self::sample_count_to_usize(sample_count)),
+            skia_safe::gpu::SurfaceOrigin::TopLeft,
+            None,
+            false,
+        )
+        .unwrap();
+
+        // `FlushRead` always hands back skia's single-sample *resolve* texture, even when the
+        // render target above is multisampled - `sample_count` only describes the MSAA target
+        // skia renders into internally, not the image we're about to wrap here. Telling rafx
+        // this shared image is N-sample when it's actually 1-sample produces a mismatched image
+        // view, so the wrapped texture is always declared SampleCount1.
+        let texture = surface
+            .get_backend_texture(skia_safe::surface::BackendHandleAccess::FlushRead)
+            .as_ref()
+            .unwrap()
+            .clone();
+        let image = Self::get_image_from_skia_texture(&texture);
+
+        let raw_image = RafxRawImageVulkan {
+            allocation: None,
+            image
+        };
+
+        let image = rafx::api::vulkan::RafxTextureVulkan::from_existing(
+            device_context.vk_device_context().unwrap(),
+            Some(raw_image),
+            &RafxTextureDef {
+                extents: RafxExtents3D {
+                    width: extents.width,
+                    height: extents.height,
+                    depth: 1
+                },
+                format,
+                resource_type: RafxResourceType::TEXTURE,
+                sample_count: RafxSampleCount::SampleCount1,
+                ..Default::default()
+            }
+        )?;
+
+        let image = resource_manager.resources().insert_image(RafxTexture::Vk(image));
+        let image_view = resource_manager.resources().get_or_create_image_view(&image, None)?;
+
+        Ok(VkSkiaSurface {
+            device_context: device_context.clone(),
+            surface,
+            texture,
+            image_view,
+            color_type,
+            format,
+            color_space,
+        })
+    }
+}
+
+#[cfg(test)]
+mod vk_skia_surface_tests {
+    use super::*;
+
+    #[test]
+    fn color_type_to_format_maps_every_negotiable_color_type() {
+        let cases = [
+            (skia_safe::ColorType::RGBA8888, RafxFormat::R8G8B8A8_UNORM),
+            (skia_safe::ColorType::BGRA8888, RafxFormat::B8G8R8A8_UNORM),
+            (skia_safe::ColorType::RGBA1010102, RafxFormat::R10G10B10A2_UNORM),
+            (skia_safe::ColorType::RGBAF16, RafxFormat::R16G16B16A16_SFLOAT),
+        ];
+
+        for (color_type, expected) in cases.iter().copied() {
+            assert_eq!(VkSkiaSurface::color_type_to_format(color_type), Some(expected));
+        }
+    }
+
+    #[test]
+    fn color_type_to_format_rejects_color_types_with_no_rafx_mapping() {
+        assert_eq!(
+            VkSkiaSurface::color_type_to_format(skia_safe::ColorType::Alpha8),
+            None
+        );
+        assert_eq!(
+            VkSkiaSurface::color_type_to_format(skia_safe::ColorType::Gray8),
+            None
+        );
+    }
+
+    #[test]
+    fn sample_count_to_usize_matches_every_variant() {
+        let cases = [
+            (RafxSampleCount::SampleCount1, 1),
+            (RafxSampleCount::SampleCount2, 2),
+            (RafxSampleCount::SampleCount4, 4),
+            (RafxSampleCount::SampleCount8, 8),
+            (RafxSampleCount::SampleCount16, 16),
+            (RafxSampleCount::SampleCount32, 32),
+        ];
+
+        for (sample_count, expected) in cases.iter().copied() {
+            assert_eq!(VkSkiaSurface::sample_count_to_usize(sample_count), expected);
+        }
+    }
+
+    #[test]
+    fn next_lower_sample_count_steps_down_one_notch_at_a_time() {
+        let steps = [
+            (RafxSampleCount::SampleCount32, RafxSampleCount::SampleCount16),
+            (RafxSampleCount::SampleCount16, RafxSampleCount::SampleCount8),
+            (RafxSampleCount::SampleCount8, RafxSampleCount::SampleCount4),
+            (RafxSampleCount::SampleCount4, RafxSampleCount::SampleCount2),
+            (RafxSampleCount::SampleCount2, RafxSampleCount::SampleCount1),
+        ];
+
+        for (from, expected) in steps.iter().copied() {
+            assert_eq!(VkSkiaSurface::next_lower_sample_count(from), Some(expected));
+        }
+    }
+
+    #[test]
+    fn next_lower_sample_count_bottoms_out_at_one() {
+        assert_eq!(
+            VkSkiaSurface::next_lower_sample_count(RafxSampleCount::SampleCount1),
+            None
+        );
+    }
+}
+
+/// CPU-rasterized skia context, used when a vulkan-backed context is unavailable or denylisted.
+/// Raster surfaces don't share any GPU context state, so this is little more than a marker that
+/// lets [`SkiaBackend`] record which path was chosen.
+pub struct RasterSkiaContext;
+
+impl RasterSkiaContext {
+    pub fn new() -> Self {
+        RasterSkiaContext
+    }
+}
+
+impl Default for RasterSkiaContext {
+    fn default() -> Self {
+        RasterSkiaContext::new()
+    }
+}
+
+/// Selects between a GPU (vulkan or metal) skia context and a CPU raster fallback. Construct
+/// once at startup via [`SkiaBackend::new`]; the active variant then determines whether
+/// [`VkSkiaSurface`], [`MetalSkiaSurface`], or [`RasterSkiaSurface`] is used to create per-frame
+/// surfaces.
+pub enum SkiaBackend {
+    Vulkan(VkSkiaContext),
+    #[cfg(target_os = "macos")]
+    Metal(MetalSkiaContext),
+    Raster(RasterSkiaContext),
+}
+
+impl SkiaBackend {
+    pub fn new(
+        device_context: &RafxDeviceContext,
+        queue: &RafxQueue,
+        config: &VkSkiaContextConfig,
+    ) -> Self {
+        // On macOS, prefer Metal when rafx was initialized with the Metal API, avoiding the
+        // MoltenVK translation layer (and its VMA version workaround) entirely.
+        #[cfg(target_os = "macos")]
+        {
+            if device_context.metal_device_context().is_some() {
+                return SkiaBackend::Metal(MetalSkiaContext::new(device_context, queue));
+            }
+        }
+
+        match VkSkiaContext::new(device_context, queue, config) {
+            VkSkiaContextCreateResult::Vulkan(context) => SkiaBackend::Vulkan(context),
+            VkSkiaContextCreateResult::Denied => SkiaBackend::Raster(RasterSkiaContext::new()),
+        }
+    }
+}
+
+/// CPU-rasterized sibling of [`VkSkiaSurface`]. Canvas draws land in an in-memory skia raster
+/// surface; [`RasterSkiaSurface::upload`] then blits the resulting pixmap into a `RafxTexture`
+/// through a staging buffer so the rest of the render graph consumes the same
+/// `ResourceArc<ImageViewResource>` it would from the vulkan path.
+///
+/// This is a degraded path relative to the GPU backends: every [`upload`](Self::upload) submits
+/// a copy, and the next upload waits on a fence for that copy to finish before reusing the
+/// command buffer, so frame pacing is bound by the CPU rasterizer and the upload round-trip
+/// rather than by GPU rendering. It exists to keep rendering working at all when Vulkan/Metal are unavailable, not to
+/// match their throughput.
+pub struct RasterSkiaSurface {
+    pub device_context: RafxDeviceContext,
+    pub image_view: ResourceArc<ImageViewResource>,
+    pub surface: skia_safe::Surface,
+    image: ResourceArc<ImageResource>,
+    staging_buffer: RafxBuffer,
+    command_pool: RafxCommandPool,
+    command_buffer: RafxCommandBuffer,
+    upload_fence: RafxFence,
+}
+
+impl RasterSkiaSurface {
+    pub fn new(
+        resource_manager: &ResourceManager,
+        queue: &RafxQueue,
+        extents: RafxExtents2D,
     ) -> RafxResult<Self> {
-        // The "native" color type is based on platform. For example, on Windows it's BGR and on
-        // MacOS it's RGB
-        let color_type = skia_safe::ColorType::n32();
+        let color_type = skia_safe::ColorType::RGBA8888;
+        let alpha_type = skia_safe::AlphaType::Premul;
+        let color_space = Some(skia_safe::ColorSpace::new_srgb_linear());
+
+        let image_info = skia_safe::ImageInfo::new(
+            (extents.width as i32, extents.height as i32),
+            color_type,
+            alpha_type,
+            color_space,
+        );
+
+        let surface = skia_safe::Surface::new_raster(&image_info, None, None).ok_or_else(|| {
+            RafxError::StringError("Failed to allocate raster skia surface".to_string())
+        })?;
+
+        let device_context = resource_manager.device_context();
+
+        let texture = device_context.create_texture(&RafxTextureDef {
+            extents: RafxExtents3D {
+                width: extents.width,
+                height: extents.height,
+                depth: 1,
+            },
+            format: RafxFormat::R8G8B8A8_UNORM,
+            resource_type: RafxResourceType::TEXTURE,
+            sample_count: RafxSampleCount::SampleCount1,
+            ..Default::default()
+        })?;
+
+        let staging_buffer = device_context.create_buffer(&RafxBufferDef {
+            size: extents.width as u64 * extents.height as u64 * 4,
+            memory_usage: RafxMemoryUsage::CpuToGpu,
+            resource_type: RafxResourceType::BUFFER,
+            ..Default::default()
+        })?;
+
+        // Allocated once and reused every upload() rather than per-frame: a fresh command pool
+        // and a full queue idle every frame would otherwise stall the GPU on this fallback path.
+        let mut command_pool = queue.create_command_pool(&RafxCommandPoolDef { transient: false })?;
+        let command_buffer =
+            command_pool.create_command_buffer(&RafxCommandBufferDef { is_secondary: false })?;
+        let upload_fence = device_context.create_fence()?;
+
+        let image = resource_manager.resources().insert_image(texture);
+        let image_view = resource_manager
+            .resources()
+            .get_or_create_image_view(&image, None)?;
+
+        Ok(RasterSkiaSurface {
+            device_context: device_context.clone(),
+            surface,
+            image,
+            staging_buffer,
+            command_pool,
+            command_buffer,
+            upload_fence,
+            image_view,
+        })
+    }
+
+    /// Copies the current CPU pixmap into the backing `RafxTexture` via the staging buffer. Call
+    /// once per frame after drawing and before the render graph reads `image_view`.
+    ///
+    /// The only GPU-side synchronization this provides is the in-command-buffer
+    /// `COPY_DST` -> `SHADER_RESOURCE` barrier; completion is observed CPU-side via
+    /// `upload_fence`, and no semaphore is signaled for another queue to wait on. The caller MUST
+    /// submit the render graph that reads `image_view` on this same `queue`, after this call
+    /// returns, so that queue submission order (not a semaphore) is what orders the copy before
+    /// the read. Consuming `image_view` from a different queue is a race.
+    pub fn upload(
+        &mut self,
+        queue: &RafxQueue,
+    ) -> RafxResult<()> {
+        let pixmap = self.surface.peek_pixels().ok_or_else(|| {
+            RafxError::StringError("Failed to peek raster surface pixels".to_string())
+        })?;
+        let bytes = pixmap
+            .bytes()
+            .ok_or_else(|| RafxError::StringError("Raster surface pixels not readable".to_string()))?;
+
+        self.staging_buffer.copy_to_host_visible_buffer(bytes)?;
+
+        // Wait for the previous upload to finish before reusing its command buffer - this is the
+        // one synchronization point on this path, traded off against allocating fresh command
+        // buffers (and stalling the whole queue) every frame.
+        if self.upload_fence.submitted() {
+            self.upload_fence.wait_for_completion()?;
+        }
+        self.command_pool.reset_command_pool()?;
+
+        let texture = &self.image.get_raw().image;
+
+        self.command_buffer.begin()?;
+        self.command_buffer.cmd_resource_barrier(
+            &[],
+            &[RafxTextureBarrier::state_transition(
+                texture,
+                RafxResourceState::UNDEFINED,
+                RafxResourceState::COPY_DST,
+            )],
+        )?;
+        self.command_buffer.cmd_copy_buffer_to_texture(
+            &self.staging_buffer,
+            texture,
+            &RafxCmdCopyBufferToTextureParams::default(),
+        )?;
+        self.command_buffer.cmd_resource_barrier(
+            &[],
+            &[RafxTextureBarrier::state_transition(
+                texture,
+                RafxResourceState::COPY_DST,
+                RafxResourceState::SHADER_RESOURCE,
+            )],
+        )?;
+        self.command_buffer.end()?;
+
+        queue.submit(&[&self.command_buffer], &[], &[], Some(&self.upload_fence))?;
+
+        Ok(())
+    }
+}
+
+/// Handles setting up skia to use the same Metal device/queue rafx initialized. This avoids
+/// routing macOS through the Vulkan/MoltenVK translation layer (and the VMA version workaround
+/// that requires, see [`VkSkiaContext::enumerate_instance_version_hooked`]).
+#[cfg(target_os = "macos")]
+pub struct MetalSkiaContext {
+    pub context: skia_safe::gpu::DirectContext,
+}
+
+#[cfg(target_os = "macos")]
+impl MetalSkiaContext {
+    pub fn new(
+        device_context: &RafxDeviceContext,
+        queue: &RafxQueue,
+    ) -> Self {
+        let metal_device_context = device_context.metal_device_context().unwrap();
+        let mtl_device = metal_device_context.device();
+        let mtl_queue = queue.metal_queue().unwrap().queue();
+
+        info!("Setting up skia backend context using Metal");
+
+        let backend_context = unsafe {
+            skia_safe::gpu::mtl::BackendContext::new(
+                mtl_device.as_ptr() as *mut c_void,
+                mtl_queue.as_ptr() as *mut c_void,
+                std::ptr::null(),
+            )
+        };
+
+        let context = skia_safe::gpu::DirectContext::new_metal(&backend_context, None).unwrap();
+
+        MetalSkiaContext { context }
+    }
+}
+
+/// Metal-backed sibling of [`VkSkiaSurface`]. Skia owns the `MTLTexture` directly (no
+/// MoltenVK translation), which is handed to rafx as a `RafxTexture::Metal` via
+/// `from_existing` so the rest of the render graph is backend-agnostic.
+#[cfg(target_os = "macos")]
+pub struct MetalSkiaSurface {
+    pub device_context: RafxDeviceContext,
+    pub image_view: ResourceArc<ImageViewResource>,
+    pub surface: skia_safe::Surface,
+    pub texture: skia_safe::gpu::BackendTexture,
+}
+
+#[cfg(target_os = "macos")]
+impl MetalSkiaSurface {
+    pub fn new(
+        resource_manager: &ResourceManager,
+        context: &mut MetalSkiaContext,
+        extents: RafxExtents2D,
+    ) -> RafxResult<Self> {
+        // Metal's native surface layout is BGRA, unlike the RGBA Vulkan tends to prefer
+        let color_type = skia_safe::ColorType::BGRA8888;
         let alpha_type = skia_safe::AlphaType::Premul;
         let color_space = Some(skia_safe::ColorSpace::new_srgb_linear());
 
@@ -143,47 +873,43 @@ impl VkSkiaSurface {
             .as_ref()
             .unwrap()
             .clone();
-        let image = Self::get_image_from_skia_texture(&texture);
+        let mtl_texture = texture.metal_texture_info().unwrap().texture();
 
-        // According to docs, kN32_SkColorType can only be kRGBA_8888_SkColorType or
-        // kBGRA_8888_SkColorType. Whatever it is, we need to set up the image view with the
-        // matching format
         let format = match color_type {
-            skia_safe::ColorType::RGBA8888 => RafxFormat::R8G8B8A8_UNORM,
             skia_safe::ColorType::BGRA8888 => RafxFormat::B8G8R8A8_UNORM,
+            skia_safe::ColorType::RGBA8888 => RafxFormat::R8G8B8A8_UNORM,
             _ => {
                 warn!("Unexpected native color type {:?}", color_type);
-                RafxFormat::R8G8B8A8_UNORM
+                RafxFormat::B8G8R8A8_UNORM
             }
         };
 
         let device_context = resource_manager.device_context();
 
-        let raw_image = RafxRawImageVulkan {
-            allocation: None,
-            image
+        let raw_image = RafxRawImageMetal {
+            texture: mtl_texture,
         };
 
-        let image = rafx::api::vulkan::RafxTextureVulkan::from_existing(
-            device_context.vk_device_context().unwrap(),
+        let image = rafx::api::metal::RafxTextureMetal::from_existing(
+            device_context.metal_device_context().unwrap(),
             Some(raw_image),
             &RafxTextureDef {
                 extents: RafxExtents3D {
                     width: extents.width,
                     height: extents.height,
-                    depth: 1
+                    depth: 1,
                 },
                 format,
                 resource_type: RafxResourceType::TEXTURE,
                 sample_count: RafxSampleCount::SampleCount1,
                 ..Default::default()
-            }
+            },
         )?;
 
-        let image = resource_manager.resources().insert_image(RafxTexture::Vk(image));
+        let image = resource_manager.resources().insert_image(RafxTexture::Metal(image));
         let image_view = resource_manager.resources().get_or_create_image_view(&image, None)?;
 
-        Ok(VkSkiaSurface {
+        Ok(MetalSkiaSurface {
             device_context: device_context.clone(),
             surface,
             texture,